@@ -138,6 +138,7 @@ fn decode_response_to_daily_data_columnar_format(
 }
 
 /// Fetch daily weather data into a Daily Data Columnar Format.
+#[allow(clippy::too_many_arguments)]
 pub async fn fetch_weather_data(
     url_base: &str,
     location: &Location,
@@ -174,6 +175,25 @@ pub async fn fetch_weather_data(
     Ok(daily)
 }
 
+/// Estimate the API call weight of `fetch_all_summable_precipitation_data`.
+pub fn estimate_summable_precipitation_call_weight(
+    weather_data_source: WeatherDataSource,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> f64 {
+    let model_count = crate::models::models_for_weather_data_source(weather_data_source).len();
+    let measure_count =
+        crate::models::daily_summable_precipitation_measures_for_weather_data_source(
+            weather_data_source,
+        )
+        .len();
+
+    crate::usage::estimate_call_weight(
+        model_count * measure_count,
+        (end_date - start_date).num_days() + 1,
+    )
+}
+
 /// Fetch all summable precipitation measures for all models.
 pub async fn fetch_all_summable_precipitation_data(
     weather_data_source: WeatherDataSource,
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use chrono::NaiveDate;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::Colorize;
 use log::debug;
 use polars::prelude::*;
@@ -10,6 +10,7 @@ mod fetch_data;
 mod geocoding;
 mod models;
 mod url_fetch;
+mod usage;
 
 use fetch_data::{DailyDataColumnarFormat, MeasureAndModel, WeatherDataSource};
 use geocoding::Location;
@@ -17,7 +18,11 @@ use geocoding::Location;
 #[derive(Parser, Debug)]
 #[command(name = "power-user-weather")]
 #[command(about = "Analyze and compare precipitation data from multiple sources", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// City name (e.g., "Seattle, WA" or "New York")
     #[arg(short, long, group = "location")]
     city: Option<String>,
@@ -31,12 +36,12 @@ struct Cli {
     lon: Option<f64>,
 
     /// Start date (YYYY-MM-DD)
-    #[arg(short, long)]
-    start: String,
+    #[arg(short, long, required = true)]
+    start: Option<String>,
 
     /// End date (YYYY-MM-DD)
-    #[arg(short, long)]
-    end: String,
+    #[arg(short, long, required = true)]
+    end: Option<String>,
 
     /// Precipitation unit (mm or inch)
     #[arg(short = 'u', long, default_value = "mm")]
@@ -63,6 +68,12 @@ struct Cli {
     verbose: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show Open-Meteo API calls made today and this month versus the free tier limits
+    Usage,
+}
+
 struct DataSourceResult {
     source: WeatherDataSource,
     data: DailyDataColumnarFormat,
@@ -139,11 +150,17 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    if let Some(Command::Usage) = cli.command {
+        return usage::print_usage_report();
+    }
+
     // Parse dates
-    let start_date = NaiveDate::parse_from_str(&cli.start, "%Y-%m-%d")
-        .context("Invalid start date format. Use YYYY-MM-DD")?;
-    let end_date = NaiveDate::parse_from_str(&cli.end, "%Y-%m-%d")
-        .context("Invalid end date format. Use YYYY-MM-DD")?;
+    let start_date =
+        NaiveDate::parse_from_str(cli.start.as_deref().expect("required by clap"), "%Y-%m-%d")
+            .context("Invalid start date format. Use YYYY-MM-DD")?;
+    let end_date =
+        NaiveDate::parse_from_str(cli.end.as_deref().expect("required by clap"), "%Y-%m-%d")
+            .context("Invalid end date format. Use YYYY-MM-DD")?;
 
     if end_date < start_date {
         anyhow::bail!("End date must be after start date");
@@ -180,22 +197,63 @@ async fn main() -> Result<()> {
     let is_forecast = start_date <= now + chrono::Duration::days(16);
     let is_mixed = start_date < now && end_date >= now;
 
-    // Collect all precipitation data
-    let mut all_data: Vec<DataSourceResult> = Vec::new();
-
-    // Fetch historical data
-    if cli.historical && (is_historical || is_mixed) {
-        println!("{}", "📊 Fetching historical data...".yellow());
+    let historical_range = (cli.historical && (is_historical || is_mixed)).then(|| {
         let hist_end = if is_mixed {
             now - chrono::Duration::days(1)
         } else {
             end_date
         };
+        (start_date, hist_end)
+    });
+
+    let forecast_range = (cli.forecast && is_forecast).then(|| {
+        let forecast_start = if is_mixed { now } else { start_date };
+        let forecast_end = if end_date > now + chrono::Duration::days(16) {
+            now + chrono::Duration::days(16)
+        } else {
+            end_date
+        };
+        (forecast_start, forecast_end)
+    });
+
+    // Warn before fetching if this run could exceed the free tier limits
+    let mut planned_fetches = Vec::new();
+    if let Some((hist_start, hist_end)) = historical_range {
+        planned_fetches.push((WeatherDataSource::HistoricalArchive, hist_start, hist_end));
+    }
+    if let Some((forecast_start, forecast_end)) = forecast_range {
+        planned_fetches.push((
+            WeatherDataSource::ForecastStandard,
+            forecast_start,
+            forecast_end,
+        ));
+        if cli.ensemble {
+            planned_fetches.push((
+                WeatherDataSource::ForecastEnsemble,
+                forecast_start,
+                forecast_end,
+            ));
+        }
+    }
+    let planned_weighted_calls: f64 = planned_fetches
+        .iter()
+        .map(|&(source, start, end)| {
+            fetch_data::estimate_summable_precipitation_call_weight(source, start, end)
+        })
+        .sum();
+    usage::warn_if_over_free_tier(planned_weighted_calls);
+
+    // Collect all precipitation data
+    let mut all_data: Vec<DataSourceResult> = Vec::new();
+
+    // Fetch historical data
+    if let Some((hist_start, hist_end)) = historical_range {
+        println!("{}", "📊 Fetching historical data...".yellow());
 
         match fetch_data::fetch_all_summable_precipitation_data(
             WeatherDataSource::HistoricalArchive,
             &location,
-            start_date,
+            hist_start,
             hist_end,
             precipitation_unit.clone(),
             &cli.timezone,
@@ -214,14 +272,8 @@ async fn main() -> Result<()> {
     }
 
     // Fetch forecast data
-    if cli.forecast && is_forecast {
+    if let Some((forecast_start, forecast_end)) = forecast_range {
         println!("{}", "🔮 Fetching forecast data...".yellow());
-        let forecast_start = if is_mixed { now } else { start_date };
-        let forecast_end = if end_date > now + chrono::Duration::days(16) {
-            now + chrono::Duration::days(16)
-        } else {
-            end_date
-        };
 
         // Standard forecast
         match fetch_data::fetch_all_summable_precipitation_data(
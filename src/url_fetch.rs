@@ -1,4 +1,5 @@
 use anyhow::Result;
+use colored::Colorize;
 use directories::ProjectDirs;
 use log::debug;
 use reqwest::Client;
@@ -27,6 +28,16 @@ pub async fn fetch_url_cached(url: &str) -> Result<String> {
     debug!("Fetching URL from API: {}", url);
     let client = Client::new();
     let response = client.get(url).send().await?;
+
+    // Record the call for usage accounting, without failing the fetch.
+    // Recorded before checking the status, as error responses still count against the limits.
+    if let Err(e) = crate::usage::record_call(url) {
+        println!(
+            "{}",
+            format!("⚠ Could not record API call in usage ledger: {:#}", e).yellow()
+        );
+    }
+
     let response = response.error_for_status()?;
     let body = response.text().await?;
 
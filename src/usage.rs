@@ -0,0 +1,534 @@
+use anyhow::{Context as _, Result};
+use chrono::{Datelike, NaiveDate, Utc};
+use colored::Colorize;
+use directories::ProjectDirs;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Open-Meteo free tier limit on (weighted) API calls per day.
+pub const FREE_TIER_DAILY_LIMIT: f64 = 10_000.0;
+
+/// Open-Meteo free tier limit on (weighted) API calls per month.
+pub const FREE_TIER_MONTHLY_LIMIT: f64 = 300_000.0;
+
+/// Open-Meteo counts a request as one call up to this many variables.
+const VARIABLES_PER_CALL: f64 = 10.0;
+
+/// Open-Meteo counts a request as one call up to this many days of data.
+const DAYS_PER_CALL: f64 = 14.0;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UsageCount {
+    /// Number of HTTP requests actually sent (cache hits are not counted).
+    pub calls: u64,
+
+    /// Estimated number of calls as Open-Meteo counts them against the limits.
+    pub weighted_calls: f64,
+}
+
+impl UsageCount {
+    fn add(&mut self, other: UsageCount) {
+        self.calls += other.calls;
+        self.weighted_calls += other.weighted_calls;
+    }
+}
+
+/// Local ledger of API calls made, by day and by endpoint.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UsageLedger {
+    /// Keyed by UTC date (YYYY-MM-DD), then by endpoint (host and path).
+    days: BTreeMap<String, BTreeMap<String, UsageCount>>,
+}
+
+impl UsageLedger {
+    /// Load the ledger from disk, or an empty one if none exists yet.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&ledger_file_path()?)
+    }
+
+    /// Load the ledger from the given path.
+    /// A ledger that can't be parsed (e.g. truncated by an interrupted write) is moved
+    /// aside so that accounting can start fresh instead of failing on every call.
+    fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)?;
+        match serde_json::from_str(&contents) {
+            Ok(ledger) => Ok(ledger),
+            Err(e) => {
+                let corrupt_path = path.with_extension("json.corrupt");
+                fs::rename(path, &corrupt_path).with_context(|| {
+                    format!("Failed to move aside corrupt usage ledger: {:?}", path)
+                })?;
+                println!(
+                    "{}",
+                    format!(
+                        "⚠ Usage ledger could not be parsed ({}); moved it to {:?} and started fresh",
+                        e, corrupt_path
+                    )
+                    .yellow()
+                );
+                Ok(Self::default())
+            }
+        }
+    }
+
+    /// Write the ledger to disk.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&ledger_file_path()?)
+    }
+
+    /// Write the ledger to the given path.
+    /// Writes to a temporary file first and renames it over the ledger, so an
+    /// interrupted write never leaves a truncated ledger behind.
+    fn save_to(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, date: NaiveDate, endpoint: &str, weighted_calls: f64) {
+        self.days
+            .entry(date.to_string())
+            .or_default()
+            .entry(endpoint.to_string())
+            .or_default()
+            .add(UsageCount {
+                calls: 1,
+                weighted_calls,
+            });
+    }
+
+    /// Usage on the given day, by endpoint.
+    pub fn day_by_endpoint(&self, date: NaiveDate) -> BTreeMap<String, UsageCount> {
+        self.days
+            .get(&date.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Usage in the calendar month containing the given day, by endpoint.
+    pub fn month_by_endpoint(&self, date: NaiveDate) -> BTreeMap<String, UsageCount> {
+        let month_prefix = format!("{:04}-{:02}-", date.year(), date.month());
+
+        let mut by_endpoint: BTreeMap<String, UsageCount> = BTreeMap::new();
+        for (_, endpoints) in self
+            .days
+            .iter()
+            .filter(|(day, _)| day.starts_with(&month_prefix))
+        {
+            for (endpoint, count) in endpoints {
+                by_endpoint.entry(endpoint.clone()).or_default().add(*count);
+            }
+        }
+        by_endpoint
+    }
+}
+
+/// Sum usage over all endpoints.
+pub fn total(by_endpoint: &BTreeMap<String, UsageCount>) -> UsageCount {
+    let mut total = UsageCount::default();
+    for count in by_endpoint.values() {
+        total.add(*count);
+    }
+    total
+}
+
+/// Build the ledger file path.
+fn ledger_file_path() -> Result<PathBuf> {
+    let proj_dirs = ProjectDirs::from("com", "example", "power-user-weather")
+        .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+
+    let data_dir = proj_dirs.data_dir();
+    fs::create_dir_all(data_dir)?;
+
+    Ok(data_dir.join("usage_ledger.json"))
+}
+
+/// Estimate how many calls Open-Meteo counts a single request as.
+/// Requests with more than 10 variables or more than 2 weeks of data count as
+/// proportionally more than one call.
+pub fn estimate_call_weight(variable_count: usize, day_count: i64) -> f64 {
+    let variable_factor = (variable_count as f64 / VARIABLES_PER_CALL).max(1.0);
+    let day_factor = (day_count as f64 / DAYS_PER_CALL).max(1.0);
+    variable_factor * day_factor
+}
+
+/// Estimate the call weight of a request URL from its query parameters.
+/// Each model counts separately for each requested variable.
+fn estimate_url_call_weight(url: &Url) -> f64 {
+    let query: BTreeMap<_, _> = url.query_pairs().collect();
+
+    let count_list = |key: &str| {
+        query
+            .get(key)
+            .map_or(0, |v| v.split(',').filter(|s| !s.is_empty()).count())
+    };
+
+    let variable_count = count_list("daily") + count_list("hourly") + count_list("current");
+    let model_count = count_list("models").max(1);
+
+    let parse_date = |key: &str| {
+        query
+            .get(key)
+            .and_then(|v| NaiveDate::parse_from_str(v, "%Y-%m-%d").ok())
+    };
+    let day_count = match (parse_date("start_date"), parse_date("end_date")) {
+        (Some(start), Some(end)) => (end - start).num_days() + 1,
+        _ => 1,
+    };
+
+    estimate_call_weight(variable_count * model_count, day_count)
+}
+
+/// Record a single API call to the given URL in the ledger.
+pub fn record_call(url: &str) -> Result<()> {
+    let parsed = Url::parse(url)?;
+    let endpoint = format!(
+        "{}{}",
+        parsed.host_str().unwrap_or("unknown"),
+        parsed.path()
+    );
+    let weighted_calls = estimate_url_call_weight(&parsed);
+
+    let mut ledger = UsageLedger::load()?;
+    ledger.record(Utc::now().date_naive(), &endpoint, weighted_calls);
+    ledger.save()?;
+
+    debug!(
+        "Recorded API call to {} (weight {:.1})",
+        endpoint, weighted_calls
+    );
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExceededLimit {
+    pub period: &'static str,
+    pub used: f64,
+    pub limit: f64,
+}
+
+/// Free tier limits that would be exceeded by making calls with the given total
+/// weight on the given day.
+pub fn exceeded_limits(
+    ledger: &UsageLedger,
+    date: NaiveDate,
+    planned_weighted_calls: f64,
+) -> Vec<ExceededLimit> {
+    let checks = [
+        (
+            "daily",
+            total(&ledger.day_by_endpoint(date)).weighted_calls,
+            FREE_TIER_DAILY_LIMIT,
+        ),
+        (
+            "monthly",
+            total(&ledger.month_by_endpoint(date)).weighted_calls,
+            FREE_TIER_MONTHLY_LIMIT,
+        ),
+    ];
+
+    checks
+        .into_iter()
+        .filter(|(_, used, limit)| used + planned_weighted_calls > *limit)
+        .map(|(period, used, limit)| ExceededLimit {
+            period,
+            used,
+            limit,
+        })
+        .collect()
+}
+
+/// Warn if making calls with the given total weight would exceed the free tier limits.
+/// An unreadable ledger is reported and treated as empty, so that it never blocks
+/// fetching data nor hides a run that alone exceeds the limits.
+pub fn warn_if_over_free_tier(planned_weighted_calls: f64) {
+    let ledger = UsageLedger::load().unwrap_or_else(|e| {
+        println!(
+            "{}",
+            format!("⚠ Could not read usage ledger: {:#}", e).yellow()
+        );
+        UsageLedger::default()
+    });
+
+    for exceeded in exceeded_limits(&ledger, Utc::now().date_naive(), planned_weighted_calls) {
+        println!(
+            "{}",
+            format!(
+                "⚠ This run may use up to {:.1} API calls; with {:.1} already used, \
+                 that exceeds the free tier {} limit of {:.0}",
+                planned_weighted_calls, exceeded.used, exceeded.period, exceeded.limit
+            )
+            .red()
+        );
+    }
+}
+
+/// Print calls made today and this month against the free tier limits.
+pub fn print_usage_report() -> Result<()> {
+    let ledger = UsageLedger::load()?;
+    let today = Utc::now().date_naive();
+
+    let periods = [
+        (
+            format!("TODAY ({})", today),
+            ledger.day_by_endpoint(today),
+            FREE_TIER_DAILY_LIMIT,
+        ),
+        (
+            format!("THIS MONTH ({})", today.format("%Y-%m")),
+            ledger.month_by_endpoint(today),
+            FREE_TIER_MONTHLY_LIMIT,
+        ),
+    ];
+
+    for (title, by_endpoint, limit) in periods {
+        println!("{}", "═".repeat(100).bright_blue());
+        println!("{}", format!("API USAGE {}", title).bright_blue().bold());
+        println!("{}", "═".repeat(100).bright_blue());
+        println!();
+
+        for (endpoint, count) in &by_endpoint {
+            println!(
+                "  {}: {} calls ({:.1} weighted)",
+                endpoint, count.calls, count.weighted_calls
+            );
+        }
+
+        let total = total(&by_endpoint);
+        let percent = total.weighted_calls / limit * 100.0;
+        let summary = format!(
+            "  Total: {} calls ({:.1} weighted) of {:.0} free tier limit ({:.1}%)",
+            total.calls, total.weighted_calls, limit, percent
+        );
+        if percent >= 100.0 {
+            println!("{}", summary.red());
+        } else if percent >= 80.0 {
+            println!("{}", summary.yellow());
+        } else {
+            println!("{}", summary.green());
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn small_requests_count_as_one_call() {
+        assert_eq!(estimate_call_weight(4, 7), 1.0);
+        assert_eq!(estimate_call_weight(0, 1), 1.0);
+    }
+
+    #[test]
+    fn large_requests_count_as_multiple_calls() {
+        assert_eq!(estimate_call_weight(20, 7), 2.0);
+        assert_eq!(estimate_call_weight(10, 28), 2.0);
+        assert_eq!(estimate_call_weight(30, 42), 9.0);
+    }
+
+    #[test]
+    fn url_weight_counts_each_model_variable_pair() {
+        let url = Url::parse(
+            "https://archive-api.open-meteo.com/v1/archive?latitude=1&longitude=2&\
+             start_date=2026-01-01&end_date=2026-01-28&daily=rain_sum,snowfall_sum&\
+             models=era5,era5_land,cerra,ecmwf_ifs,best_match",
+        )
+        .unwrap();
+
+        // 2 measures * 5 models = 10 variables, 28 days = 2 blocks of 14 days.
+        assert_eq!(estimate_url_call_weight(&url), 2.0);
+    }
+
+    #[test]
+    fn url_weight_of_geocoding_is_one_call() {
+        let url = Url::parse(
+            "https://geocoding-api.open-meteo.com/v1/search?name=Seattle&count=1&language=en&format=json",
+        )
+        .unwrap();
+
+        assert_eq!(estimate_url_call_weight(&url), 1.0);
+    }
+
+    #[test]
+    fn ledger_totals_by_day_and_month() {
+        let mut ledger = UsageLedger::default();
+        ledger.record(date("2026-02-13"), "api.open-meteo.com/v1/forecast", 24.0);
+        ledger.record(date("2026-02-13"), "api.open-meteo.com/v1/forecast", 24.0);
+        ledger.record(
+            date("2026-02-13"),
+            "geocoding-api.open-meteo.com/v1/search",
+            1.0,
+        );
+        ledger.record(date("2026-02-01"), "api.open-meteo.com/v1/forecast", 2.0);
+        ledger.record(date("2026-01-31"), "api.open-meteo.com/v1/forecast", 100.0);
+
+        let day = ledger.day_by_endpoint(date("2026-02-13"));
+        assert_eq!(
+            day["api.open-meteo.com/v1/forecast"],
+            UsageCount {
+                calls: 2,
+                weighted_calls: 48.0
+            }
+        );
+        assert_eq!(
+            total(&day),
+            UsageCount {
+                calls: 3,
+                weighted_calls: 49.0
+            }
+        );
+
+        let month = ledger.month_by_endpoint(date("2026-02-13"));
+        assert_eq!(
+            total(&month),
+            UsageCount {
+                calls: 4,
+                weighted_calls: 51.0
+            }
+        );
+    }
+
+    #[test]
+    fn no_limits_exceeded_for_small_run() {
+        let mut ledger = UsageLedger::default();
+        ledger.record(date("2026-02-13"), "api.open-meteo.com/v1/forecast", 24.0);
+
+        assert!(exceeded_limits(&ledger, date("2026-02-13"), 100.0).is_empty());
+    }
+
+    #[test]
+    fn daily_limit_exceeded_by_usage_plus_planned() {
+        let mut ledger = UsageLedger::default();
+        ledger.record(
+            date("2026-02-13"),
+            "api.open-meteo.com/v1/forecast",
+            9_990.0,
+        );
+        // Yesterday's usage counts towards the month but not today.
+        ledger.record(
+            date("2026-02-12"),
+            "api.open-meteo.com/v1/forecast",
+            9_990.0,
+        );
+
+        assert_eq!(
+            exceeded_limits(&ledger, date("2026-02-13"), 20.0),
+            vec![ExceededLimit {
+                period: "daily",
+                used: 9_990.0,
+                limit: FREE_TIER_DAILY_LIMIT,
+            }]
+        );
+    }
+
+    #[test]
+    fn monthly_limit_exceeded_by_earlier_days() {
+        let mut ledger = UsageLedger::default();
+        ledger.record(
+            date("2026-02-01"),
+            "archive-api.open-meteo.com/v1/archive",
+            299_000.0,
+        );
+        // Last month's usage does not count.
+        ledger.record(
+            date("2026-01-31"),
+            "archive-api.open-meteo.com/v1/archive",
+            299_000.0,
+        );
+
+        assert_eq!(
+            exceeded_limits(&ledger, date("2026-02-13"), 2_000.0),
+            vec![ExceededLimit {
+                period: "monthly",
+                used: 299_000.0,
+                limit: FREE_TIER_MONTHLY_LIMIT,
+            }]
+        );
+    }
+
+    #[test]
+    fn limits_exceeded_by_planned_run_alone() {
+        let ledger = UsageLedger::default();
+
+        let exceeded = exceeded_limits(&ledger, date("2026-02-13"), 400_000.0);
+
+        assert_eq!(
+            exceeded.iter().map(|e| e.period).collect::<Vec<_>>(),
+            vec!["daily", "monthly"]
+        );
+        assert!(exceeded.iter().all(|e| e.used == 0.0));
+    }
+
+    /// Unique path in the system temp dir for a test ledger.
+    fn temp_ledger_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "power-user-weather-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("usage_ledger.json")
+    }
+
+    #[test]
+    fn ledger_round_trips_through_disk() {
+        let path = temp_ledger_path("round_trip");
+        let mut ledger = UsageLedger::default();
+        ledger.record(date("2026-02-13"), "api.open-meteo.com/v1/forecast", 24.0);
+        ledger.save_to(&path).unwrap();
+
+        let loaded = UsageLedger::load_from(&path).unwrap();
+
+        assert_eq!(
+            loaded.day_by_endpoint(date("2026-02-13")),
+            ledger.day_by_endpoint(date("2026-02-13"))
+        );
+        assert!(!path.with_extension("json.tmp").exists());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn corrupt_ledger_is_moved_aside_and_started_fresh() {
+        let path = temp_ledger_path("corrupt");
+        fs::write(&path, r#"{"days":"#).unwrap();
+
+        let ledger = UsageLedger::load_from(&path).expect("Expected fresh ledger");
+
+        assert!(ledger.day_by_endpoint(date("2026-02-13")).is_empty());
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(path.with_extension("json.corrupt")).unwrap(),
+            r#"{"days":"#
+        );
+
+        // Accounting continues normally after recovery.
+        let mut ledger = ledger;
+        ledger.record(date("2026-02-13"), "api.open-meteo.com/v1/forecast", 1.0);
+        ledger.save_to(&path).unwrap();
+        assert!(UsageLedger::load_from(&path).is_ok());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn ledger_is_empty_for_day_without_calls() {
+        let ledger = UsageLedger::default();
+        assert!(ledger.day_by_endpoint(date("2026-02-13")).is_empty());
+    }
+}